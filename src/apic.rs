@@ -39,3 +39,6 @@
 
 pub mod ioapic;
 pub mod lapic;
+
+#[cfg(feature = "acpi")]
+pub mod madt;
@@ -1,5 +1,4 @@
 use bit_field::BitField;
-use bitflags::bitflags;
 use core::ptr::NonNull;
 use volatile::VolatileRef;
 
@@ -15,26 +14,114 @@ const IA_VER_REG: u8 = 0x01;
 /// I/O APIC arbitration register
 const IA_ARB_REG: u8 = 0x02;
 
-bitflags! {
-    /// Information stored in bits 8 to 10 of the redirection table entry register for an IRQ
-    /// that determines how an interrupt will be sent to the CPU.
-    pub struct DeliveryMode: u8 {
-        const FIXED = 1 << 0;
-        const LOW_PRIORITY = 1 << 1;
-        const SMI = 1 << 2;
-        const NMI = 1 << 3;
-        const INIT = 0b101;
-        const EXTINIT = 0b111;
-    }
+/// Information stored in bits 8 to 10 of the redirection table entry register for an IRQ
+/// that determines how an interrupt will be sent to the CPU. The discriminants are the 3-bit
+/// field encodings the hardware expects.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DeliveryMode {
+    Fixed = 0b000,
+    LowestPriority = 0b001,
+    Smi = 0b010,
+    Nmi = 0b100,
+    Init = 0b101,
+    ExtInit = 0b111,
+}
+
+/// The interpretation of the destination field of a redirection table entry, stored in bit 11.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DestinationMode {
+    /// The destination field contains the APIC ID of a single CPU.
+    Physical,
+    /// The destination field contains a set of processors.
+    Logical,
+}
+
+/// The signal level that raises an interrupt on a line, stored in bit 13.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PinPolarity {
+    ActiveHigh,
+    ActiveLow,
+}
+
+/// How the line signals an interrupt, stored in bit 15.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TriggerMode {
+    Edge,
+    Level,
 }
 
 /// Redirection table entry for an IRQ.
+///
+/// Build an entry with [`RedirectionTableEntry::new`] and the `set_*` methods, then hand it to
+/// [`IoApic::write_redirection_entry`]. Each entry occupies two 32-bit registers: the `low` word
+/// carries the vector, delivery, polarity, trigger, and mask fields, while the `high` word carries
+/// the destination APIC ID. A freshly created entry is masked, so it can be configured and written
+/// before a valid destination is chosen.
 #[derive(Debug, Copy, Clone)]
 pub struct RedirectionTableEntry {
     high: u32,
     low: u32,
 }
 
+impl RedirectionTableEntry {
+    /// Create a masked, zeroed redirection table entry.
+    pub const fn new() -> Self {
+        // Start masked so the entry can be programmed before a destination is valid.
+        Self {
+            high: 0,
+            low: 1 << 16,
+        }
+    }
+
+    /// Set the interrupt vector delivered to the CPU (bits 0 to 7).
+    pub fn set_vector(&mut self, vector: u8) -> &mut Self {
+        self.low.set_bits(0..8, vector as u32);
+        self
+    }
+
+    /// Set the delivery mode (bits 8 to 10).
+    pub fn set_delivery_mode(&mut self, mode: DeliveryMode) -> &mut Self {
+        self.low.set_bits(8..11, mode as u32);
+        self
+    }
+
+    /// Set the destination mode (bit 11).
+    pub fn set_destination_mode(&mut self, mode: DestinationMode) -> &mut Self {
+        self.low.set_bit(11, mode == DestinationMode::Logical);
+        self
+    }
+
+    /// Set the pin polarity (bit 13).
+    pub fn set_pin_polarity(&mut self, polarity: PinPolarity) -> &mut Self {
+        self.low.set_bit(13, polarity == PinPolarity::ActiveLow);
+        self
+    }
+
+    /// Set the trigger mode (bit 15).
+    pub fn set_trigger_mode(&mut self, mode: TriggerMode) -> &mut Self {
+        self.low.set_bit(15, mode == TriggerMode::Level);
+        self
+    }
+
+    /// Mask or unmask this entry (bit 16). A masked entry does not raise interrupts.
+    pub fn set_masked(&mut self, masked: bool) -> &mut Self {
+        self.low.set_bit(16, masked);
+        self
+    }
+
+    /// Set the destination APIC ID (bits 24 to 31 of the high word).
+    pub fn set_destination(&mut self, apic_id: u8) -> &mut Self {
+        self.high.set_bits(24..32, apic_id as u32);
+        self
+    }
+}
+
+impl Default for RedirectionTableEntry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// A single I/O APIC.
 pub struct IoApic {
     /// The register select register, used to access the rest of the I/O APIC's registers.
@@ -89,4 +176,24 @@ impl IoApic {
     pub fn arbitration_id(&mut self) -> u8 {
         self.read_reg(IA_ARB_REG).get_bits(24..28) as u8
     }
+
+    /// Write `entry` into the redirection table slot for the given IRQ.
+    ///
+    /// The high word (carrying the destination) is written before the low word (carrying the
+    /// vector and mask) so the entry is never briefly live with a valid vector but a stale
+    /// destination.
+    pub fn write_redirection_entry(&mut self, irq: u8, entry: RedirectionTableEntry) {
+        let reg = RDT_BASE + irq * 2;
+        self.write_reg(reg + 1, entry.high);
+        self.write_reg(reg, entry.low);
+    }
+
+    /// Read the redirection table entry for the given IRQ.
+    pub fn read_redirection_entry(&mut self, irq: u8) -> RedirectionTableEntry {
+        let reg = RDT_BASE + irq * 2;
+        RedirectionTableEntry {
+            low: self.read_reg(reg),
+            high: self.read_reg(reg + 1),
+        }
+    }
 }
@@ -0,0 +1,191 @@
+use bit_field::BitField;
+use x86_64::registers::model_specific::Msr;
+
+/// The `IA32_APIC_BASE` model-specific register, which holds the physical base of the Local APIC.
+const IA32_APIC_BASE: u32 = 0x1B;
+
+/// The default physical base of the Local APIC MMIO window.
+pub const DEFAULT_BASE: usize = 0xFEE0_0000;
+
+/// Spurious Interrupt Vector Register.
+const REG_SIVR: usize = 0xF0;
+
+/// End of Interrupt register.
+const REG_EOI: usize = 0xB0;
+
+/// Interrupt Command Register, low half.
+const REG_ICR_LOW: usize = 0x300;
+
+/// Interrupt Command Register, high half.
+const REG_ICR_HIGH: usize = 0x310;
+
+/// LVT Timer register.
+const REG_LVT_TIMER: usize = 0x320;
+
+/// Timer Initial Count register.
+const REG_TIMER_INITIAL: usize = 0x380;
+
+/// Timer Current Count register.
+const REG_TIMER_CURRENT: usize = 0x390;
+
+/// Timer Divide Configuration register.
+const REG_TIMER_DIVIDE: usize = 0x3E0;
+
+/// How the Local APIC timer reloads its count.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TimerMode {
+    /// Count down once and stop.
+    OneShot,
+    /// Count down and reload from the initial count repeatedly.
+    Periodic,
+}
+
+/// The divisor applied to the Local APIC timer's input clock, encoded in the Divide Configuration
+/// register.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DivideConfig {
+    Div1,
+    Div2,
+    Div4,
+    Div8,
+    Div16,
+    Div32,
+    Div64,
+    Div128,
+}
+
+impl DivideConfig {
+    /// The bit pattern written to the Divide Configuration register.
+    const fn bits(self) -> u32 {
+        match self {
+            DivideConfig::Div1 => 0b1011,
+            DivideConfig::Div2 => 0b0000,
+            DivideConfig::Div4 => 0b0001,
+            DivideConfig::Div8 => 0b0010,
+            DivideConfig::Div16 => 0b0011,
+            DivideConfig::Div32 => 0b1000,
+            DivideConfig::Div64 => 0b1001,
+            DivideConfig::Div128 => 0b1010,
+        }
+    }
+}
+
+/// A Local APIC, accessed over its memory-mapped register window.
+pub struct LocalApic {
+    base: usize,
+}
+
+impl LocalApic {
+    /// Create a Local APIC over the MMIO window at `base_addr`.
+    ///
+    /// # Safety
+    ///
+    /// `base_addr` must be the valid, identity-mapped base of the Local APIC register window.
+    pub unsafe fn new(base_addr: usize) -> Self {
+        Self { base: base_addr }
+    }
+
+    /// Create a Local APIC using the physical base reported by the `IA32_APIC_BASE` MSR.
+    ///
+    /// # Safety
+    ///
+    /// The Local APIC base reported by the MSR must be valid and identity-mapped.
+    pub unsafe fn from_msr() -> Self {
+        // The physical base lives in bits 12 and up of the MSR value.
+        let base = (Msr::new(IA32_APIC_BASE).read() & 0x000F_FFFF_FFFF_F000) as usize;
+        Self { base }
+    }
+
+    /// Read the register at `reg` (a byte offset into the MMIO window).
+    pub fn read_reg(&self, reg: usize) -> u32 {
+        // SAFETY: `reg` is a valid register offset within the window established at construction.
+        unsafe { core::ptr::read_volatile((self.base + reg) as *const u32) }
+    }
+
+    /// Write `val` to the register at `reg` (a byte offset into the MMIO window).
+    pub fn write_reg(&mut self, reg: usize, val: u32) {
+        // SAFETY: `reg` is a valid register offset within the window established at construction.
+        unsafe { core::ptr::write_volatile((self.base + reg) as *mut u32, val) }
+    }
+
+    /// Enable the Local APIC by setting bit 8 of the Spurious Interrupt Vector Register along with
+    /// the spurious interrupt vector the CPU should receive for spurious interrupts.
+    pub fn enable(&mut self, spurious_vector: u8) {
+        let mut sivr = self.read_reg(REG_SIVR);
+        sivr.set_bits(0..8, spurious_vector as u32);
+        sivr.set_bit(8, true);
+        self.write_reg(REG_SIVR, sivr);
+    }
+
+    /// Signal the end of the interrupt currently being serviced by writing zero to the EOI
+    /// register.
+    pub fn end_of_interrupt(&mut self) {
+        self.write_reg(REG_EOI, 0);
+    }
+
+    /// Program the Local APIC timer with the given vector, mode, divisor, and initial count.
+    ///
+    /// Writing a non-zero initial count starts the timer; a one-shot timer counts down once while
+    /// a periodic timer reloads from the initial count each time it reaches zero. Use
+    /// [`LocalApic::calibrate`] to translate a real time interval into an initial count.
+    pub fn set_timer(
+        &mut self,
+        vector: u8,
+        mode: TimerMode,
+        divide: DivideConfig,
+        initial_count: u32,
+    ) {
+        self.write_reg(REG_TIMER_DIVIDE, divide.bits());
+
+        let mut lvt = 0u32;
+        lvt.set_bits(0..8, vector as u32);
+        lvt.set_bit(17, mode == TimerMode::Periodic);
+        self.write_reg(REG_LVT_TIMER, lvt);
+
+        self.write_reg(REG_TIMER_INITIAL, initial_count);
+    }
+
+    /// Measure how many timer ticks elapse over a known interval so periodic timers can be set in
+    /// real time units.
+    ///
+    /// The divisor is programmed, the initial count is set to its maximum, and `wait` is called to
+    /// busy-wait the known interval (for example, timed against the PIT). The current count is
+    /// then read and subtracted from the maximum to yield the ticks that elapsed during the
+    /// interval.
+    pub fn calibrate(&mut self, divide: DivideConfig, wait: impl FnOnce()) -> u32 {
+        self.write_reg(REG_TIMER_DIVIDE, divide.bits());
+        self.write_reg(REG_TIMER_INITIAL, u32::MAX);
+
+        wait();
+
+        u32::MAX - self.read_reg(REG_TIMER_CURRENT)
+    }
+
+    /// Write the Interrupt Command Register, which dispatches an inter-processor interrupt. The
+    /// high half (destination) is written before the low half (command), since writing the low
+    /// half is what sends the IPI.
+    fn write_icr(&mut self, dest_apic: u8, low: u32) {
+        let mut high = 0u32;
+        high.set_bits(24..32, dest_apic as u32);
+        self.write_reg(REG_ICR_HIGH, high);
+        self.write_reg(REG_ICR_LOW, low);
+    }
+
+    /// Send a fixed-delivery inter-processor interrupt with the given vector to another processor.
+    pub fn send_ipi(&mut self, dest_apic: u8, vector: u8) {
+        self.write_icr(dest_apic, vector as u32);
+    }
+
+    /// Send an INIT IPI to another processor, the first step of multiprocessor bring-up.
+    pub fn send_init(&mut self, dest_apic: u8) {
+        // INIT delivery mode (0b101) with level assert.
+        self.write_icr(dest_apic, 0x4500);
+    }
+
+    /// Send a STARTUP IPI carrying the `vector` entry-point page to another processor, the second
+    /// step of multiprocessor bring-up.
+    pub fn send_startup(&mut self, dest_apic: u8, vector: u8) {
+        // STARTUP delivery mode (0b110) with the entry-point vector in the low bits.
+        self.write_icr(dest_apic, 0x4600 | vector as u32);
+    }
+}
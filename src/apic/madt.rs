@@ -0,0 +1,137 @@
+//! MADT-driven construction of I/O APICs.
+//!
+//! The [`apic`](crate::apic) module docs recommend parsing the MADT with the
+//! [acpi](https://docs.rs/acpi/latest/acpi/index.html) crate, but leave the bridge from its parsed
+//! tables to a working set of [`IoApic`]s up to the caller. This module closes that gap: it
+//! consumes the APIC interrupt model the `acpi` crate produces from a `PlatformInfo`, maps every
+//! reported I/O APIC at its MMIO base, records the ISA Interrupt Source Overrides, and resolves
+//! ISA IRQs to the right I/O APIC pin when routing an interrupt.
+//!
+//! This module is gated behind the `acpi` Cargo feature.
+
+use acpi::platform::interrupt::{Apic, Polarity, TriggerMode as AcpiTriggerMode};
+use alloc::vec::Vec;
+
+use super::ioapic::{
+    DeliveryMode, DestinationMode, IoApic, PinPolarity, RedirectionTableEntry, TriggerMode,
+};
+
+/// An I/O APIC together with the global system interrupt range it owns.
+struct Entry {
+    ioapic: IoApic,
+    gsi_base: u32,
+    gsi_count: u32,
+}
+
+/// A single ISA Interrupt Source Override, resolving an ISA IRQ to a global system interrupt and
+/// carrying the polarity and trigger mode the firmware reported for it.
+struct IsaOverride {
+    isa_irq: u8,
+    gsi: u32,
+    polarity: PinPolarity,
+    trigger: TriggerMode,
+}
+
+/// The set of I/O APICs described by the MADT, ready to route ISA IRQs.
+pub struct IoApics {
+    entries: Vec<Entry>,
+    overrides: Vec<IsaOverride>,
+}
+
+impl IoApics {
+    /// Build the set of [`IoApic`]s from the APIC interrupt model parsed by the `acpi` crate.
+    ///
+    /// Each reported I/O APIC is mapped at its MMIO base and queried for the size of its
+    /// redirection table so its global system interrupt range is known. The ISA Interrupt Source
+    /// Overrides are retained for use by [`IoApics::route_isa_irq`].
+    ///
+    /// # Safety
+    ///
+    /// The MMIO base of every I/O APIC in `apic` must be a valid, identity-mapped address.
+    pub unsafe fn from_apic(apic: &Apic) -> Self {
+        let entries = apic
+            .io_apics
+            .iter()
+            .map(|io| {
+                // SAFETY: the caller guarantees the reported base is a valid address.
+                let mut ioapic = unsafe { IoApic::new(io.address as usize) };
+                let gsi_count = ioapic.irqs() as u32 + 1;
+
+                Entry {
+                    ioapic,
+                    gsi_base: io.global_system_interrupt_base,
+                    gsi_count,
+                }
+            })
+            .collect();
+
+        let overrides = apic
+            .interrupt_source_overrides
+            .iter()
+            .map(|iso| IsaOverride {
+                isa_irq: iso.isa_source,
+                gsi: iso.global_system_interrupt,
+                polarity: polarity_from_acpi(iso.polarity),
+                trigger: trigger_from_acpi(iso.trigger_mode),
+            })
+            .collect();
+
+        Self { entries, overrides }
+    }
+
+    /// Route an ISA IRQ to the given vector on the given destination APIC.
+    ///
+    /// The ISA IRQ is first resolved to its global system interrupt: if an override exists (for
+    /// example the common IRQ0 to GSI2 remap) its GSI, polarity, and trigger mode are used,
+    /// otherwise the GSI equals the ISA IRQ with the ISA defaults of active-high, edge-triggered.
+    /// The I/O APIC whose GSI range contains that GSI is selected, the local pin is computed as
+    /// `gsi - gsi_base`, and its redirection entry is programmed accordingly.
+    ///
+    /// Returns `false` if no I/O APIC owns the resolved GSI.
+    pub fn route_isa_irq(&mut self, isa_irq: u8, vector: u8, dest_apic: u8) -> bool {
+        let (gsi, polarity, trigger) = match self.overrides.iter().find(|o| o.isa_irq == isa_irq) {
+            Some(o) => (o.gsi, o.polarity, o.trigger),
+            None => (isa_irq as u32, PinPolarity::ActiveHigh, TriggerMode::Edge),
+        };
+
+        let entry = match self
+            .entries
+            .iter_mut()
+            .find(|e| gsi >= e.gsi_base && gsi < e.gsi_base + e.gsi_count)
+        {
+            Some(entry) => entry,
+            None => return false,
+        };
+
+        let pin = (gsi - entry.gsi_base) as u8;
+        let mut rte = RedirectionTableEntry::new();
+        rte.set_vector(vector)
+            .set_delivery_mode(DeliveryMode::Fixed)
+            .set_destination_mode(DestinationMode::Physical)
+            .set_pin_polarity(polarity)
+            .set_trigger_mode(trigger)
+            .set_destination(dest_apic)
+            .set_masked(false);
+
+        entry.ioapic.write_redirection_entry(pin, rte);
+        true
+    }
+}
+
+/// Convert the `acpi` crate's polarity into the redirection entry polarity, treating the
+/// bus-default as the ISA default of active-high.
+fn polarity_from_acpi(polarity: Polarity) -> PinPolarity {
+    match polarity {
+        Polarity::ActiveLow => PinPolarity::ActiveLow,
+        Polarity::ActiveHigh | Polarity::SameAsBus => PinPolarity::ActiveHigh,
+    }
+}
+
+/// Convert the `acpi` crate's trigger mode into the redirection entry trigger mode, treating the
+/// bus-default as the ISA default of edge-triggered.
+fn trigger_from_acpi(trigger: AcpiTriggerMode) -> TriggerMode {
+    match trigger {
+        AcpiTriggerMode::Level => TriggerMode::Level,
+        AcpiTriggerMode::Edge | AcpiTriggerMode::SameAsBus => TriggerMode::Edge,
+    }
+}
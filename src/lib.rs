@@ -11,12 +11,16 @@
 //! ## Crate Features
 //! - `8259pic` - Enable interface for the legacy 8259 PIC
 //! - `apic` - Enable interface for the newer APIC
+//! - `acpi` - Build the set of I/O APICs from a MADT parsed by the [acpi](https://docs.rs/acpi) crate
 
 #![no_std]
 
 #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
 compile_error!("This crate will only work on x86 platforms");
 
+#[cfg(feature = "acpi")]
+extern crate alloc;
+
 #[cfg(feature = "8259pic")]
 pub mod pic8259;
 
@@ -56,7 +56,9 @@
 //! Next, initialize the PICs (make sure interrupts are disabled):
 //!
 //! ```rust
-//! unsafe { PICS.lock().initialize(); }
+//! use complete_pic::pic8259::PicMode;
+//!
+//! unsafe { PICS.lock().initialize(PicMode::Normal); }
 //! # enable interrupts after initializing the PIC
 //! ```
 //!
@@ -80,6 +82,7 @@
 //! Some bootloaders might mask all the IRQs from the 8259 (legacy) PIC, like Limine. Make sure you check the bootloader's documentation before
 //! you become confused due to this module not functioning as expected.
 
+use bit_field::BitField;
 use x86_64::instructions::port::Port;
 
 /// The command I/O port of the master PIC.
@@ -100,9 +103,21 @@ const PIC_INIT: u8 = 0x11;
 /// PIC End of Interrupt command.
 const PIC_EIO: u8 = 0x20;
 
+/// Base of the specific EOI command. The low three bits select the in-service IRQ to acknowledge.
+const PIC_SPECIFIC_EOI: u8 = 0x60;
+
 /// The PIC 8086 mode.
 const PIC_MODE_8086: u8 = 0x01;
 
+/// Automatic EOI bit set in the ICW4 byte to enable Automatic EOI mode.
+const PIC_AUTO_EOI: u8 = 0x02;
+
+/// OCW3 command to read the In-Service Register on the next command port read.
+const OCW3_READ_ISR: u8 = 0x0B;
+
+/// OCW3 command to read the Interrupt Request Register on the next command port read.
+const OCW3_READ_IRR: u8 = 0x0A;
+
 /// An individual PIC chip.
 struct Pic {
     /// The vector offset of the PIC chip.
@@ -137,6 +152,20 @@ impl Pic {
         self.command.write(PIC_EIO);
     }
 
+    /// Signal that a specific IRQ has been handled, acknowledging exactly that in-service
+    /// interrupt rather than the highest-priority one. Required when interrupts of different
+    /// priorities are nested.
+    unsafe fn end_of_interrupt_specific(&mut self, irq_id: u8) {
+        self.command.write(PIC_SPECIFIC_EOI | (irq_id - self.offset));
+    }
+
+    /// Read one of this PIC's status registers by issuing OCW3 to the command port and then
+    /// reading the value it latches back onto the command port.
+    unsafe fn read_status(&mut self, ocw3: u8) -> u8 {
+        self.command.write(ocw3);
+        self.command.read()
+    }
+
     /// Read the interrupt mask of this PIC. When no command is issued, we can access the PIC's
     /// interrupt mask via its data I/O port.
     unsafe fn read_interrupt_mask(&mut self) -> u8 {
@@ -150,6 +179,16 @@ impl Pic {
     }
 }
 
+/// How the PICs acknowledge interrupts, selected when calling [`ChainedPics::initialize`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PicMode {
+    /// Normal 8086 mode. Each interrupt must be acknowledged with an explicit EOI.
+    Normal,
+    /// Automatic EOI mode. The PIC clears the in-service bit by itself, so callers never issue an
+    /// EOI. Only appropriate when no nested interrupt priority is required.
+    AutoEoi,
+}
+
 /// The two 8259 PICs, chained together.
 pub struct ChainedPics {
     pics: [Pic; 2],
@@ -181,10 +220,13 @@ impl ChainedPics {
     /// - <https://www.eeeguide.com/8259-programmable-interrupt-controller>
     /// - <https://www.thesatya.com/8259.html>
     ///
+    /// The `mode` selects whether interrupts are acknowledged explicitly ([`PicMode::Normal`]) or
+    /// automatically ([`PicMode::AutoEoi`]); it controls the AEOI bit of the ICW4 byte.
+    ///
     /// # Safety
     ///
     /// Please read the Safety section of [`ChainedPics::new`].
-    pub unsafe fn initialize(&mut self) {
+    pub unsafe fn initialize(&mut self, mode: PicMode) {
         // We need to add a delay between writes to our PICs, especially on
         // older motherboards. But we don't necessarily have any kind of
         // timers yet, because most of them require interrupts. Various
@@ -217,10 +259,14 @@ impl ChainedPics {
         self.pics[1].data.write(2);
         wait();
 
-        // Byte 3: Set the PIC mode.
-        self.pics[0].data.write(PIC_MODE_8086);
+        // Byte 3: Set the PIC mode, optionally enabling Automatic EOI.
+        let icw4 = match mode {
+            PicMode::Normal => PIC_MODE_8086,
+            PicMode::AutoEoi => PIC_MODE_8086 | PIC_AUTO_EOI,
+        };
+        self.pics[0].data.write(icw4);
         wait();
-        self.pics[1].data.write(PIC_MODE_8086);
+        self.pics[1].data.write(icw4);
         wait();
 
         // Restore the saved masks.
@@ -246,6 +292,50 @@ impl ChainedPics {
         self.write_interrupt_masks(u8::MAX, u8::MAX);
     }
 
+    /// Mask (disable) a single IRQ line in the range 0 to 15.
+    ///
+    /// Lines 0 to 7 live on the master PIC and lines 8 to 15 on the slave. The relevant chip's
+    /// mask is read via its data port, the single bit is set, and the mask is written back. When
+    /// the last slave line is masked, the master's cascade line (IRQ2) is re-masked as well, since
+    /// no slave interrupt can propagate through it anymore.
+    pub unsafe fn mask_irq(&mut self, irq_line: u8) {
+        let (pic, bit) = if irq_line < 8 {
+            (0, irq_line)
+        } else {
+            (1, irq_line - 8)
+        };
+
+        let mut mask = self.pics[pic].read_interrupt_mask();
+        mask.set_bit(bit as usize, true);
+        self.pics[pic].write_interrupt_mask(mask);
+
+        if pic == 1 && mask == u8::MAX {
+            self.mask_irq(2);
+        }
+    }
+
+    /// Unmask (enable) a single IRQ line in the range 0 to 15.
+    ///
+    /// Lines 0 to 7 live on the master PIC and lines 8 to 15 on the slave. The relevant chip's
+    /// mask is read via its data port, the single bit is cleared, and the mask is written back.
+    /// Unmasking any slave line also unmasks the master's cascade line (IRQ2) so interrupts from
+    /// the slave can actually propagate to the CPU.
+    pub unsafe fn unmask_irq(&mut self, irq_line: u8) {
+        let (pic, bit) = if irq_line < 8 {
+            (0, irq_line)
+        } else {
+            (1, irq_line - 8)
+        };
+
+        let mut mask = self.pics[pic].read_interrupt_mask();
+        mask.set_bit(bit as usize, false);
+        self.pics[pic].write_interrupt_mask(mask);
+
+        if pic == 1 {
+            self.unmask_irq(2);
+        }
+    }
+
     /// Check if the master or slave PIC handles the IRQ specified by the given ID.
     pub fn handles_interrupt(&self, irq_id: u8) -> bool {
         self.pics.iter().any(|p| p.handles_interrupt(irq_id))
@@ -269,6 +359,95 @@ impl ChainedPics {
         }
     }
 
+    /// Read the In-Service Register (ISR) of both PICs. A set bit means the corresponding IRQ is
+    /// currently being serviced; the index into the returned array is `[master, slave]`.
+    pub unsafe fn read_in_service(&mut self) -> [u8; 2] {
+        [
+            self.pics[0].read_status(OCW3_READ_ISR),
+            self.pics[1].read_status(OCW3_READ_ISR),
+        ]
+    }
+
+    /// Read the Interrupt Request Register (IRR) of both PICs. A set bit means the corresponding
+    /// IRQ has been raised but not yet serviced; the index into the returned array is
+    /// `[master, slave]`.
+    pub unsafe fn read_irq_request(&mut self) -> [u8; 2] {
+        [
+            self.pics[0].read_status(OCW3_READ_IRR),
+            self.pics[1].read_status(OCW3_READ_IRR),
+        ]
+    }
+
+    /// Check whether the interrupt with the given ID is spurious.
+    ///
+    /// The legacy PICs raise a spurious IRQ7 (master) or IRQ15 (slave) under noisy conditions. A
+    /// spurious interrupt is detected by reading the relevant chip's ISR: if the top bit (bit 7)
+    /// is clear, no real interrupt is in service and the IRQ is spurious.
+    ///
+    /// For a spurious master interrupt no EOI must be sent at all. For a spurious slave interrupt
+    /// an EOI must still be sent to the master, because the master recorded a real cascade
+    /// interrupt on IRQ2. Callers should consult this before acknowledging an interrupt and use
+    /// [`ChainedPics::notify_spurious_interrupt`] to perform the correct EOI, if any.
+    ///
+    /// # Safety
+    ///
+    /// Please read the Safety section of [`ChainedPics::notify_end_of_interrupt`].
+    pub unsafe fn is_spurious(&mut self, irq_id: u8) -> bool {
+        let pic = if self.pics[1].handles_interrupt(irq_id) {
+            1
+        } else if self.pics[0].handles_interrupt(irq_id) {
+            0
+        } else {
+            return false;
+        };
+
+        // Only the lowest-priority line of each chip (IRQ7 / IRQ15) can be spurious.
+        if irq_id - self.pics[pic].offset != 7 {
+            return false;
+        }
+
+        !self.pics[pic].read_status(OCW3_READ_ISR).get_bit(7)
+    }
+
+    /// Companion to [`ChainedPics::notify_end_of_interrupt`] for an interrupt that
+    /// [`ChainedPics::is_spurious`] reported as spurious.
+    ///
+    /// A spurious master interrupt gets no EOI. A spurious slave interrupt gets an EOI to the
+    /// master only, acknowledging the real cascade interrupt the master recorded on IRQ2, while
+    /// the slave EOI is skipped.
+    ///
+    /// # Safety
+    ///
+    /// Please read the Safety section of [`ChainedPics::notify_end_of_interrupt`].
+    pub unsafe fn notify_spurious_interrupt(&mut self, irq_id: u8) {
+        if self.pics[1].handles_interrupt(irq_id) {
+            self.pics[0].end_of_interrupt();
+        }
+    }
+
+    /// Acknowledge an interrupt with a specific EOI, which clears exactly the in-service bit for
+    /// `irq_id` rather than the highest-priority one. This is the correct acknowledgement when
+    /// interrupts of different priorities are nested.
+    ///
+    /// If the IRQ originated from the slave PIC, a specific EOI is sent to the slave and a
+    /// specific EOI for the cascade line (IRQ2) is sent to the master. Otherwise a single specific
+    /// EOI is sent to the master.
+    ///
+    /// # Safety
+    ///
+    /// Please read the Safety section of [`ChainedPics::notify_end_of_interrupt`].
+    pub unsafe fn notify_end_of_interrupt_specific(&mut self, irq_id: u8) {
+        if self.handles_interrupt(irq_id) {
+            if self.pics[1].handles_interrupt(irq_id) {
+                self.pics[1].end_of_interrupt_specific(irq_id);
+                let cascade = self.pics[0].offset + 2;
+                self.pics[0].end_of_interrupt_specific(cascade);
+            } else {
+                self.pics[0].end_of_interrupt_specific(irq_id);
+            }
+        }
+    }
+
     /// Restore the vector offsets to the defaults, which do not conflict with anything in real mode.
     #[doc(hidden)]
     pub fn restore(&mut self) {